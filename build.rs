@@ -4,31 +4,82 @@ use std::env;
 use std::path::PathBuf;
 use std::process::Command;
 
+// Locates the SplinterDB checkout to build against. `SPLINTERDB_DIR` lets
+// anyone point at their own checkout; absent that we fall back to the
+// vendored `splinterdb` submodule next to this crate, so a fresh clone still
+// builds without extra setup.
+fn splinterdb_dir() -> PathBuf {
+    if let Ok(dir) = env::var("SPLINTERDB_DIR") {
+        return PathBuf::from(dir);
+    }
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("splinterdb")
+}
+
 fn main() {
-    let splinterdb_path = PathBuf::from("/users/hao01/splinterdb-sys/splinterdb");
-    env::set_var("CC", "clang");
-    env::set_var("LD", "clang");
+    let splinterdb_path = splinterdb_dir();
+    let profile = if cfg!(feature = "debug-build") {
+        "debug"
+    } else {
+        "release"
+    };
+
+    // `SPLINTERDB_LIB_DIR` points at an already-built (or installed) copy of
+    // libsplinterdb, e.g. one built outside of cargo or provided by a system
+    // package. When it's set we skip running `make` entirely and just link
+    // against it.
+    let prebuilt_lib_dir = env::var("SPLINTERDB_LIB_DIR").ok().map(PathBuf::from);
+
+    let splinterdb_lib = match &prebuilt_lib_dir {
+        Some(dir) => dir.clone(),
+        None => {
+            env::set_var("CC", "clang");
+            env::set_var("LD", "clang");
+
+            // SplinterDB's Makefile doesn't have separate `debug`/`release`
+            // targets; the profile is selected by the `BUILD_MODE` variable
+            // it reads at the top, defaulting to `release` when unset. So a
+            // bare `make` already does the right thing for the release
+            // profile, and `debug-build` only needs to set that variable,
+            // not pass a goal make doesn't have.
+            env::set_var("BUILD_MODE", profile);
 
-    Command::new("make")
-        .current_dir(&splinterdb_path)
-        .status()
-        .expect("Failed to build splinterdb");
+            // Cargo sets NUM_JOBS to the build parallelism it was invoked
+            // with; forward it to `make -j` instead of building serially.
+            let jobs = env::var("NUM_JOBS").unwrap_or_else(|_| "1".to_string());
+            let status = Command::new("make")
+                .current_dir(&splinterdb_path)
+                .arg(format!("-j{jobs}"))
+                .status()
+                .expect("Failed to build splinterdb");
+            assert!(status.success(), "splinterdb build failed");
 
-    let splinterdb_lib = splinterdb_path.join("build/release/lib");
+            splinterdb_path.join("build").join(profile).join("lib")
+        }
+    };
     let splinterdb_include = splinterdb_path.join("include");
 
     println!(
         "cargo:rustc-link-search=native={}",
         splinterdb_lib.display()
     );
-    println!(
-        "cargo:rustc-link-arg=-Wl,-rpath,{}",
-        splinterdb_lib.display()
-    );
-    println!("cargo:rustc-link-lib=dylib=splinterdb");
+
+    // `static`/`dynamic` pick the link mode; dynamic (the historical
+    // default) also needs an rpath so the resulting binary can find
+    // libsplinterdb.so at runtime without LD_LIBRARY_PATH.
+    if cfg!(feature = "static") {
+        println!("cargo:rustc-link-lib=static=splinterdb");
+    } else {
+        println!(
+            "cargo:rustc-link-arg=-Wl,-rpath,{}",
+            splinterdb_lib.display()
+        );
+        println!("cargo:rustc-link-lib=dylib=splinterdb");
+    }
 
     // Tell cargo to invalidate the built crate whenever the wrapper changes
     println!("cargo:rerun-if-changed=wrapper.h");
+    println!("cargo:rerun-if-env-changed=SPLINTERDB_DIR");
+    println!("cargo:rerun-if-env-changed=SPLINTERDB_LIB_DIR");
 
     // The bindgen::Builder is the main entry point
     // to bindgen, and lets you build up options for
@@ -38,7 +89,10 @@ fn main() {
         .no_copy("writable_buffer")
         .no_copy("data_config")
         .allowlist_type("splinterdb.*")
+        .allowlist_type("transactional_splinterdb.*")
+        .allowlist_type("transaction")
         .allowlist_function("splinterdb.*")
+        .allowlist_function("transactional_splinterdb.*")
         .allowlist_function("default_data_config.*")
         .allowlist_function("merge.*")
         .allowlist_var("SPLINTERDB.*")
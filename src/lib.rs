@@ -1,12 +1,28 @@
 pub mod raw;
 
-use std::io::{Error, Result};
+use std::io::{Error, Read, Result, Write};
+use std::ops::{Bound, RangeBounds};
 use std::path::Path;
 
 pub mod rust_cfg;
 use rust_cfg::new_sdb_data_config;
 pub use rust_cfg::{CompareResult, DefaultSdb, SdbMessage, SdbMessageType, SdbRustDataFuncs};
 
+pub mod typed;
+pub use typed::{TypedKey, TypedStore, TypedValue};
+
+// Tags which Rust type a `TypedKey`/`TypedValue` byte buffer should be
+// decoded as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conversion {
+    Bytes,
+    Integer,
+    UnsignedInteger,
+    Float,
+    Boolean,
+    Timestamp,
+}
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug)]
 pub struct DBConfig {
@@ -16,6 +32,10 @@ pub struct DBConfig {
     pub max_value_size: usize,
 }
 
+// A handle to a non-transactional SplinterDB instance. For grouping writes
+// into atomic transactions, open the database as a `TransactionalSplinterDB`
+// instead -- the two are backed by distinct C handles, so a `SplinterDB` has
+// no `begin_transaction` of its own.
 #[derive(Debug)]
 pub struct SplinterDB {
     _inner: *mut raw::splinterdb,
@@ -32,6 +52,27 @@ impl Drop for SplinterDB {
     }
 }
 
+// A SplinterDB opened in transactional mode. This is a distinct C handle
+// from `SplinterDB` (created via `transactional_splinterdb_create`/`_open`
+// rather than `splinterdb_create`/`_open`), so a `SplinterDB` cannot be
+// upgraded into one after the fact -- open the database as a
+// `TransactionalSplinterDB` from the start if you need `begin_transaction`.
+#[derive(Debug)]
+pub struct TransactionalSplinterDB {
+    _inner: *mut raw::transactional_splinterdb,
+    sdb_cfg: Box<raw::splinterdb_config>,
+    data_cfg: Box<raw::data_config>,
+}
+
+unsafe impl Sync for TransactionalSplinterDB {}
+unsafe impl Send for TransactionalSplinterDB {}
+
+impl Drop for TransactionalSplinterDB {
+    fn drop(&mut self) {
+        unsafe { raw::transactional_splinterdb_close(&mut self._inner) };
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum LookupResult {
     Found(Vec<u8>),
@@ -39,6 +80,28 @@ pub enum LookupResult {
     NotFound,
 }
 
+// Backend-agnostic key-value interface. Implementing this against another
+// engine lets downstream crates write storage code once and swap `SplinterDB`
+// for a different backend.
+pub trait KvStore {
+    type Cursor<'a>: Iterator<Item = Result<(Vec<u8>, Vec<u8>)>>
+    where
+        Self: 'a;
+
+    fn insert(&self, key: &[u8], value: &[u8]) -> Result<()>;
+    fn update(&self, key: &[u8], delta: &[u8]) -> Result<()>;
+    fn delete(&self, key: &[u8]) -> Result<()>;
+    fn lookup(&self, key: &[u8]) -> Result<LookupResult>;
+    fn range(&self, start_key: Option<&[u8]>) -> Result<Self::Cursor<'_>>;
+
+    // Approximate count of entries. The default walks a full `range(None)`
+    // scan, so it's O(n) rather than a cheap metadata read; backends with a
+    // faster accessor should override it.
+    fn len(&self) -> Result<usize> {
+        Ok(self.range(None)?.count())
+    }
+}
+
 fn as_result(rc: ::std::os::raw::c_int) -> Result<()> {
     if rc != 0 {
         Err(Error::from_raw_os_error(rc))
@@ -47,6 +110,31 @@ fn as_result(rc: ::std::os::raw::c_int) -> Result<()> {
     }
 }
 
+// Shared by `SplinterDB::lookup` and `SplinterTransaction::lookup`: pulls the
+// value out of a populated `splinterdb_lookup_result`.
+unsafe fn lookup_result_from_raw(lr: &raw::splinterdb_lookup_result) -> Result<LookupResult> {
+    let found = raw::splinterdb_lookup_found(lr) as i32;
+    if found == 0 {
+        return Ok(LookupResult::NotFound);
+    }
+
+    let mut val: raw::slice = raw::slice {
+        length: 0,
+        data: std::mem::zeroed(),
+    };
+    let rc = raw::splinterdb_lookup_result_value(lr, &mut val);
+    as_result(rc)?;
+
+    // TODO: Can we avoid this memory init and copy?
+    let mut value: Vec<u8> = vec![0; val.length as usize];
+    std::ptr::copy(
+        val.data,
+        std::mem::transmute(value.as_mut_ptr()),
+        val.length as usize,
+    );
+    Ok(LookupResult::Found(value))
+}
+
 fn create_splinter_slice(ref v: &[u8]) -> raw::slice {
     unsafe {
         raw::slice {
@@ -56,6 +144,22 @@ fn create_splinter_slice(ref v: &[u8]) -> raw::slice {
     }
 }
 
+// Compares two keys using the comparator registered in `data_cfg`, the same
+// one SplinterDB itself uses to order keys on disk.
+fn compare_keys(data_cfg: &raw::data_config, a: &[u8], b: &[u8]) -> ::std::cmp::Ordering {
+    let key_compare = data_cfg
+        .key_compare
+        .expect("data_config must provide a key_compare function");
+    let rc = unsafe {
+        key_compare(
+            data_cfg as *const raw::data_config,
+            create_splinter_slice(a),
+            create_splinter_slice(b),
+        )
+    };
+    rc.cmp(&0)
+}
+
 #[derive(Debug)]
 pub struct IteratorResult<'a> {
     pub key: &'a [u8],
@@ -68,6 +172,11 @@ pub struct SplinterCursor<'a> {
     _marker: ::std::marker::PhantomData<raw::splinterdb_iterator>,
     _parent_marker: ::std::marker::PhantomData<&'a raw::splinterdb>,
     state: Option<IteratorResult<'a>>,
+    // Set by `SplinterDB::range_bounded`. Once the cursor observes a key past
+    // this bound it latches `bound_exceeded` so it keeps reporting exhaustion
+    // even though the underlying C iterator may still have more entries.
+    upper_bound: Option<(&'a raw::data_config, Vec<u8>, bool)>,
+    bound_exceeded: bool,
 }
 
 impl<'a> Drop for SplinterCursor<'a> {
@@ -85,9 +194,44 @@ impl<'a> SplinterCursor<'a> {
             _marker: ::std::marker::PhantomData,
             _parent_marker: ::std::marker::PhantomData,
             state: Self::_get_current(iter)?,
+            upper_bound: None,
+            bound_exceeded: false,
         })
     }
 
+    // Attaches an upper bound to an already-initialized cursor and clamps the
+    // current state if it already lies past it. `inclusive` tracks whether
+    // the bound came from `Bound::Included` or `Bound::Excluded`.
+    fn with_upper_bound(
+        mut self,
+        data_cfg: &'a raw::data_config,
+        end_key: Vec<u8>,
+        inclusive: bool,
+    ) -> Self {
+        self.upper_bound = Some((data_cfg, end_key, inclusive));
+        self.clamp_to_upper_bound();
+        self
+    }
+
+    fn clamp_to_upper_bound(&mut self) {
+        let (data_cfg, end_key, inclusive) = match &self.upper_bound {
+            None => return,
+            Some(bound) => (bound.0, &bound.1, bound.2),
+        };
+        let past_bound = match &self.state {
+            None => false,
+            Some(r) => match compare_keys(data_cfg, r.key, end_key) {
+                ::std::cmp::Ordering::Greater => true,
+                ::std::cmp::Ordering::Equal => !inclusive,
+                ::std::cmp::Ordering::Less => false,
+            },
+        };
+        if past_bound {
+            self.bound_exceeded = true;
+            self.state = None;
+        }
+    }
+
     // returns the current state of the iterator from the C API
     fn _get_current(it: *mut raw::splinterdb_iterator) -> Result<Option<IteratorResult<'a>>> {
         let valid: i32 = unsafe { raw::splinterdb_iterator_valid(it) } as i32;
@@ -136,6 +280,9 @@ impl<'a> SplinterCursor<'a> {
 
     #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Result<bool> {
+        if self.bound_exceeded {
+            return Ok(false);
+        }
         let can_next = unsafe { raw::splinterdb_iterator_can_next(self._inner) } as i32;
         if can_next == 0 {
             let rc = unsafe { raw::splinterdb_iterator_status(self._inner) };
@@ -147,7 +294,8 @@ impl<'a> SplinterCursor<'a> {
         }
 
         self.state = Self::_get_current(self._inner)?;
-        Ok(true)
+        self.clamp_to_upper_bound();
+        Ok(self.state.is_some())
     }
 
     #[allow(clippy::should_implement_trait)]
@@ -167,6 +315,35 @@ impl<'a> SplinterCursor<'a> {
     }
 }
 
+// `IteratorResult` borrows from the cursor, so it can't be the `Item` of a
+// standard `Iterator` (the borrow wouldn't outlive the call to `next`).
+// Instead this adapter copies each key/value pair out before advancing,
+// letting callers `for`-loop, `map`, `filter`, and `collect` over a cursor
+// the way they would over any other Rust collection.
+impl<'a> Iterator for SplinterCursor<'a> {
+    type Item = Result<(Vec<u8>, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let owned = self
+            .get_curr()
+            .map(|r| (r.key.to_vec(), r.value.to_vec()))?;
+        // `SplinterCursor::next` leaves `self.state` pointing at the last
+        // element when the underlying iterator is exhausted (`Ok(false)`) or
+        // errors out; clear it in both cases so this adapter actually
+        // terminates instead of re-yielding (or re-erroring on) that same
+        // element forever.
+        match SplinterCursor::next(self) {
+            Ok(false) => self.state = None,
+            Ok(true) => {}
+            Err(e) => {
+                self.state = None;
+                return Some(Err(e));
+            }
+        }
+        Some(Ok(owned))
+    }
+}
+
 fn path_as_cstring<P: AsRef<Path>>(path: P) -> std::ffi::CString {
     let as_os_str = path.as_ref().as_os_str();
     let as_str = as_os_str.to_str().unwrap();
@@ -259,26 +436,7 @@ impl SplinterDB {
             let rc = raw::splinterdb_lookup(self._inner, create_splinter_slice(key), &mut lr);
             as_result(rc)?;
 
-            let found = raw::splinterdb_lookup_found(&lr) as i32;
-            if found == 0 {
-                return Ok(LookupResult::NotFound);
-            }
-
-            let mut val: raw::slice = raw::slice {
-                length: 0,
-                data: std::mem::zeroed(),
-            };
-            let rc = raw::splinterdb_lookup_result_value(&lr, &mut val);
-            as_result(rc)?;
-
-            // TODO: Can we avoid this memory init and copy?
-            let mut value: Vec<u8> = vec![0; val.length as usize];
-            std::ptr::copy(
-                val.data,
-                std::mem::transmute(value.as_mut_ptr()),
-                val.length as usize,
-            );
-            Ok(LookupResult::Found(value))
+            lookup_result_from_raw(&lr)
         }
     }
 
@@ -301,6 +459,269 @@ impl SplinterDB {
         as_result(rc)?;
         return SplinterCursor::new(iter);
     }
+
+    // Like `range`, but also takes an end bound so the returned cursor stops
+    // yielding once a key passes it, instead of scanning to the end of the
+    // database. `start_bound`/`end_bound` are compared with the same
+    // comparator registered in `data_config`, so this works for any
+    // registered key ordering, not just lexicographic byte order.
+    pub fn range_bounded<'a>(&'a self, range: impl RangeBounds<&'a [u8]>) -> Result<SplinterCursor<'a>> {
+        let start_key = match range.start_bound() {
+            Bound::Included(s) => Some(*s),
+            Bound::Excluded(s) => Some(*s),
+            Bound::Unbounded => None,
+        };
+        let mut cursor = self.range(start_key)?;
+
+        // `splinterdb_iterator_init` always positions inclusively on the
+        // start key, so an excluded start bound needs one extra `next()` to
+        // skip past it.
+        if let Bound::Excluded(s) = range.start_bound() {
+            let at_start = matches!(
+                cursor.get_curr(),
+                Some(r) if compare_keys(self.data_cfg.as_ref(), r.key, s) == ::std::cmp::Ordering::Equal
+            );
+            if at_start {
+                cursor.next()?;
+            }
+        }
+
+        let upper_bound = match range.end_bound() {
+            Bound::Included(e) => Some((e.to_vec(), true)),
+            Bound::Excluded(e) => Some((e.to_vec(), false)),
+            Bound::Unbounded => None,
+        };
+        if let Some((end_key, inclusive)) = upper_bound {
+            cursor = cursor.with_upper_bound(self.data_cfg.as_ref(), end_key, inclusive);
+        }
+
+        Ok(cursor)
+    }
+
+    // Dumps every entry to `w` as a stream of length-prefixed key/value
+    // records (big-endian u32 length followed by the bytes, key then value).
+    // Pairs with `import` to back up a database or move it between
+    // `DBConfig` layouts (e.g. a different `max_key_size`) without a live
+    // process talking to both at once.
+    pub fn export<W: Write>(&self, w: &mut W) -> Result<()> {
+        for entry in self.range(None)? {
+            let (key, value) = entry?;
+            w.write_all(&(key.len() as u32).to_be_bytes())?;
+            w.write_all(&key)?;
+            w.write_all(&(value.len() as u32).to_be_bytes())?;
+            w.write_all(&value)?;
+        }
+        Ok(())
+    }
+
+    // Replays records written by `export`, inserting each one in turn.
+    pub fn import<R: Read>(&self, r: &mut R) -> Result<()> {
+        loop {
+            let mut len_buf = [0u8; 4];
+            if let Err(e) = r.read_exact(&mut len_buf) {
+                if e.kind() == std::io::ErrorKind::UnexpectedEof {
+                    return Ok(());
+                }
+                return Err(e);
+            }
+            let key = read_len_prefixed(r, u32::from_be_bytes(len_buf))?;
+
+            r.read_exact(&mut len_buf)?;
+            let value = read_len_prefixed(r, u32::from_be_bytes(len_buf))?;
+
+            self.insert(&key, &value)?;
+        }
+    }
+}
+
+impl TransactionalSplinterDB {
+    // Create a new TransactionalSplinterDB object. This is uninitialized.
+    pub fn new<T: rust_cfg::SdbRustDataFuncs>() -> TransactionalSplinterDB {
+        TransactionalSplinterDB {
+            _inner: std::ptr::null_mut(),
+            sdb_cfg: Box::new(unsafe { std::mem::zeroed() }),
+            data_cfg: Box::new(new_sdb_data_config::<T>(0)),
+        }
+    }
+
+    fn db_create_or_open<P: AsRef<Path>>(
+        &mut self,
+        path: &P,
+        cfg: &DBConfig,
+        open_existing: bool,
+    ) -> Result<()> {
+        let path = path_as_cstring(path); // don't drop until init is done
+
+        // set up the splinterdb config
+        self.sdb_cfg.filename = path.as_ptr();
+        self.sdb_cfg.cache_size = cfg.cache_size_bytes as u64;
+        self.sdb_cfg.disk_size = cfg.disk_size_bytes as u64;
+        self.sdb_cfg.data_cfg = self.data_cfg.as_mut();
+        self.sdb_cfg.num_memtable_bg_threads = 2;
+        self.sdb_cfg.num_normal_bg_threads = 2;
+        self.sdb_cfg.io_flags |= O_DIRECT;
+
+        // set key bytes
+        self.data_cfg.max_key_size = cfg.max_key_size as u64;
+
+        // Open or create the database
+        let rc = if open_existing {
+            unsafe { raw::transactional_splinterdb_open(self.sdb_cfg.as_ref(), &mut self._inner) }
+        } else {
+            unsafe {
+                raw::transactional_splinterdb_create(self.sdb_cfg.as_ref(), &mut self._inner)
+            }
+        };
+        as_result(rc)
+    }
+
+    pub fn db_create<P: AsRef<Path>>(&mut self, path: &P, cfg: &DBConfig) -> Result<()> {
+        self.db_create_or_open(path, cfg, false)
+    }
+
+    pub fn db_open<P: AsRef<Path>>(&mut self, path: &P, cfg: &DBConfig) -> Result<()> {
+        self.db_create_or_open(path, cfg, true)
+    }
+
+    // Starts a transaction that groups multiple writes atomically, instead
+    // of each `insert`/`update`/`delete` committing on its own as soon as it
+    // returns.
+    pub fn begin_transaction(&self) -> Result<SplinterTransaction> {
+        let mut txn: Box<raw::transaction> = Box::new(unsafe { std::mem::zeroed() });
+        let rc =
+            unsafe { raw::transactional_splinterdb_begin_transaction(self._inner, txn.as_mut()) };
+        as_result(rc)?;
+        Ok(SplinterTransaction {
+            db: self,
+            txn,
+            finished: false,
+        })
+    }
+}
+
+fn read_len_prefixed<R: Read>(r: &mut R, len: u32) -> Result<Vec<u8>> {
+    let mut buf = vec![0u8; len as usize];
+    r.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+impl KvStore for SplinterDB {
+    type Cursor<'a> = SplinterCursor<'a>;
+
+    fn insert(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.insert(key, value)
+    }
+
+    fn update(&self, key: &[u8], delta: &[u8]) -> Result<()> {
+        self.update(key, delta)
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<()> {
+        self.delete(key)
+    }
+
+    fn lookup(&self, key: &[u8]) -> Result<LookupResult> {
+        self.lookup(key)
+    }
+
+    fn range(&self, start_key: Option<&[u8]>) -> Result<Self::Cursor<'_>> {
+        self.range(start_key)
+    }
+}
+
+// A transaction started with `TransactionalSplinterDB::begin_transaction`.
+// Writes and reads made through it are only visible to other transactions
+// once `commit` succeeds; dropping it without calling `commit` aborts it.
+#[derive(Debug)]
+pub struct SplinterTransaction<'a> {
+    db: &'a TransactionalSplinterDB,
+    txn: Box<raw::transaction>,
+    finished: bool,
+}
+
+impl<'a> Drop for SplinterTransaction<'a> {
+    fn drop(&mut self) {
+        if !self.finished {
+            unsafe {
+                raw::transactional_splinterdb_abort_transaction(self.db._inner, self.txn.as_mut())
+            };
+        }
+    }
+}
+
+impl<'a> SplinterTransaction<'a> {
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> Result<()> {
+        let rc = unsafe {
+            raw::transactional_splinterdb_insert(
+                self.db._inner,
+                self.txn.as_mut(),
+                create_splinter_slice(key),
+                create_splinter_slice(value),
+            )
+        };
+        as_result(rc)
+    }
+
+    pub fn update(&mut self, key: &[u8], delta: &[u8]) -> Result<()> {
+        let rc = unsafe {
+            raw::transactional_splinterdb_update(
+                self.db._inner,
+                self.txn.as_mut(),
+                create_splinter_slice(key),
+                create_splinter_slice(delta),
+            )
+        };
+        as_result(rc)
+    }
+
+    pub fn delete(&mut self, key: &[u8]) -> Result<()> {
+        let rc = unsafe {
+            raw::transactional_splinterdb_delete(
+                self.db._inner,
+                self.txn.as_mut(),
+                create_splinter_slice(key),
+            )
+        };
+        as_result(rc)
+    }
+
+    pub fn lookup(&mut self, key: &[u8]) -> Result<LookupResult> {
+        unsafe {
+            let mut lr: raw::splinterdb_lookup_result = std::mem::zeroed();
+            raw::transactional_splinterdb_lookup_result_init(
+                self.db._inner,
+                &mut lr,
+                0,
+                std::ptr::null_mut(),
+            );
+
+            let rc = raw::transactional_splinterdb_lookup(
+                self.db._inner,
+                self.txn.as_mut(),
+                create_splinter_slice(key),
+                &mut lr,
+            );
+            as_result(rc)?;
+
+            lookup_result_from_raw(&lr)
+        }
+    }
+
+    pub fn commit(mut self) -> Result<()> {
+        let rc = unsafe {
+            raw::transactional_splinterdb_commit_transaction(self.db._inner, self.txn.as_mut())
+        };
+        self.finished = true;
+        as_result(rc)
+    }
+
+    pub fn abort(mut self) -> Result<()> {
+        let rc = unsafe {
+            raw::transactional_splinterdb_abort_transaction(self.db._inner, self.txn.as_mut())
+        };
+        self.finished = true;
+        as_result(rc)
+    }
 }
 
 mod tests;
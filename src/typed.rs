@@ -0,0 +1,215 @@
+// Typed key/value encoding layered on top of the raw `&[u8]` API in
+// `lib.rs`. Keys are encoded so their byte representation sorts the same way
+// the value they represent does under SplinterDB's default lexicographic
+// key comparator, so range scans over typed keys come back in numeric
+// order instead of raw-byte order.
+
+use crate::{Conversion, KvStore, LookupResult};
+use std::io::{Error, ErrorKind, Result};
+
+// A fixed-width encoding decodes from exactly 8 bytes; anything else means
+// the record wasn't produced by the matching `encode_*` (e.g. a truncated
+// value, or a `Conversion` that doesn't match what was actually stored).
+fn decode_error(bytes: &[u8]) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("expected 8 bytes to decode, got {}", bytes.len()),
+    )
+}
+
+fn fixed_width(bytes: &[u8]) -> Result<[u8; 8]> {
+    bytes.try_into().map_err(|_| decode_error(bytes))
+}
+
+// Order-preserving big-endian encoding for an unsigned integer: the default
+// comparator already orders fixed-width big-endian bytes the same way it
+// orders the numbers they represent.
+fn encode_uint(v: u64) -> [u8; 8] {
+    v.to_be_bytes()
+}
+
+fn decode_uint(bytes: &[u8]) -> Result<u64> {
+    Ok(u64::from_be_bytes(fixed_width(bytes)?))
+}
+
+// Signed integers use two's complement, where negative numbers have their
+// top bit set and so sort *after* positives under an unsigned byte compare.
+// Flipping the sign bit before encoding fixes that: negatives become
+// 0x00.. and positives 0x80.., restoring numeric order.
+fn encode_int(v: i64) -> [u8; 8] {
+    ((v as u64) ^ (1u64 << 63)).to_be_bytes()
+}
+
+fn decode_int(bytes: &[u8]) -> Result<i64> {
+    Ok((decode_uint(bytes)? ^ (1u64 << 63)) as i64)
+}
+
+// IEEE-754 floats almost sort correctly as big-endian bytes, except the sign
+// bit is inverted relative to two's complement: positives need their sign
+// bit set (so they sort after all negatives), and negatives need every bit
+// flipped (so a more-negative number, which has a larger magnitude in its
+// lower bits, sorts before a less-negative one).
+fn encode_float(v: f64) -> [u8; 8] {
+    let bits = v.to_bits();
+    let flipped = if bits & (1u64 << 63) != 0 {
+        !bits
+    } else {
+        bits | (1u64 << 63)
+    };
+    flipped.to_be_bytes()
+}
+
+fn decode_float(bytes: &[u8]) -> Result<f64> {
+    let flipped = decode_uint(bytes)?;
+    let bits = if flipped & (1u64 << 63) != 0 {
+        flipped & !(1u64 << 63)
+    } else {
+        !flipped
+    };
+    Ok(f64::from_bits(bits))
+}
+
+fn decode_bool(bytes: &[u8]) -> Result<bool> {
+    match bytes.first() {
+        Some(b) => Ok(*b != 0),
+        None => Err(Error::new(
+            ErrorKind::InvalidData,
+            "expected at least 1 byte to decode a bool, got 0",
+        )),
+    }
+}
+
+// A typed key. Encodes to a byte layout that preserves the numeric ordering
+// of the wrapped value under SplinterDB's default key comparator.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedKey {
+    Bytes(Vec<u8>),
+    UInt(u64),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Timestamp(i64),
+}
+
+impl TypedKey {
+    pub fn conversion(&self) -> Conversion {
+        match self {
+            TypedKey::Bytes(_) => Conversion::Bytes,
+            TypedKey::UInt(_) => Conversion::UnsignedInteger,
+            TypedKey::Int(_) => Conversion::Integer,
+            TypedKey::Float(_) => Conversion::Float,
+            TypedKey::Bool(_) => Conversion::Boolean,
+            TypedKey::Timestamp(_) => Conversion::Timestamp,
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            TypedKey::Bytes(b) => b.clone(),
+            TypedKey::UInt(v) => encode_uint(*v).to_vec(),
+            TypedKey::Int(v) => encode_int(*v).to_vec(),
+            TypedKey::Float(v) => encode_float(*v).to_vec(),
+            TypedKey::Bool(v) => vec![*v as u8],
+            TypedKey::Timestamp(v) => encode_int(*v).to_vec(),
+        }
+    }
+
+    pub fn decode(conversion: Conversion, bytes: &[u8]) -> Result<Self> {
+        Ok(match conversion {
+            Conversion::Bytes => TypedKey::Bytes(bytes.to_vec()),
+            Conversion::Integer => TypedKey::Int(decode_int(bytes)?),
+            Conversion::UnsignedInteger => TypedKey::UInt(decode_uint(bytes)?),
+            Conversion::Float => TypedKey::Float(decode_float(bytes)?),
+            Conversion::Boolean => TypedKey::Bool(decode_bool(bytes)?),
+            Conversion::Timestamp => TypedKey::Timestamp(decode_int(bytes)?),
+        })
+    }
+}
+
+// A typed value. Reuses `TypedKey`'s encodings for simplicity; there is no
+// ordering requirement on values, but the same fixed-width layout is a
+// convenient, unambiguous wire format.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedValue {
+    Bytes(Vec<u8>),
+    UInt(u64),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Timestamp(i64),
+}
+
+impl TypedValue {
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            TypedValue::Bytes(b) => b.clone(),
+            TypedValue::UInt(v) => encode_uint(*v).to_vec(),
+            TypedValue::Int(v) => encode_int(*v).to_vec(),
+            TypedValue::Float(v) => encode_float(*v).to_vec(),
+            TypedValue::Bool(v) => vec![*v as u8],
+            TypedValue::Timestamp(v) => encode_int(*v).to_vec(),
+        }
+    }
+
+    pub fn decode(conversion: Conversion, bytes: &[u8]) -> Result<Self> {
+        Ok(match conversion {
+            Conversion::Bytes => TypedValue::Bytes(bytes.to_vec()),
+            Conversion::Integer => TypedValue::Int(decode_int(bytes)?),
+            Conversion::UnsignedInteger => TypedValue::UInt(decode_uint(bytes)?),
+            Conversion::Float => TypedValue::Float(decode_float(bytes)?),
+            Conversion::Boolean => TypedValue::Bool(decode_bool(bytes)?),
+            Conversion::Timestamp => TypedValue::Timestamp(decode_int(bytes)?),
+        })
+    }
+}
+
+// A thin typed facade over any `KvStore`, translating `TypedKey`/`TypedValue`
+// to and from the raw `&[u8]` records the backend actually stores.
+pub struct TypedStore<'a, S: KvStore> {
+    inner: &'a S,
+}
+
+impl<'a, S: KvStore> TypedStore<'a, S> {
+    pub fn new(inner: &'a S) -> Self {
+        TypedStore { inner }
+    }
+
+    pub fn insert(&self, key: &TypedKey, value: &TypedValue) -> Result<()> {
+        self.inner.insert(&key.encode(), &value.encode())
+    }
+
+    pub fn delete(&self, key: &TypedKey) -> Result<()> {
+        self.inner.delete(&key.encode())
+    }
+
+    pub fn lookup(&self, key: &TypedKey, value_conversion: Conversion) -> Result<Option<TypedValue>> {
+        match self.inner.lookup(&key.encode())? {
+            LookupResult::Found(v) | LookupResult::FoundTruncated(v) => {
+                Ok(Some(TypedValue::decode(value_conversion, &v)?))
+            }
+            LookupResult::NotFound => Ok(None),
+        }
+    }
+
+    // Scans in key order starting at `start` (or the beginning of the
+    // database), decoding each raw record back into typed key/value pairs.
+    pub fn range(
+        &self,
+        start: Option<&TypedKey>,
+        key_conversion: Conversion,
+        value_conversion: Conversion,
+    ) -> Result<impl Iterator<Item = Result<(TypedKey, TypedValue)>> + 'a>
+    where
+        S: 'a,
+    {
+        let start_bytes = start.map(TypedKey::encode);
+        let cursor = self.inner.range(start_bytes.as_deref())?;
+        Ok(cursor.map(move |entry| {
+            let (k, v) = entry?;
+            Ok((
+                TypedKey::decode(key_conversion, &k)?,
+                TypedValue::decode(value_conversion, &v)?,
+            ))
+        }))
+    }
+}